@@ -0,0 +1,213 @@
+//! Persisted fan threshold calibration profile
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::fan::{Fan, Thresholds};
+
+/// Current on-disk profile format, bumped whenever the layout changes incompatibly
+const PROFILE_VERSION: u32 = 1;
+
+/// Calibration profile error
+#[expect(clippy::missing_docs_in_private_items)]
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum ProfileError {
+    #[error("Unsupported calibration profile version: {0} (expected {PROFILE_VERSION})")]
+    UnsupportedVersion(u32),
+    #[error(
+        "Invalid calibrated thresholds for '{0}': max_stop ({1}) must be less than min_start ({2})"
+    )]
+    InvalidThresholds(String, crate::pwm::Value, crate::pwm::Value),
+}
+
+/// Calibrated thresholds for every known fan, persisted as JSON and reloaded on startup
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Profile {
+    /// On-disk format version
+    version: u32,
+    /// Unix timestamp (seconds) of the last calibration recorded in this profile, or 0 if this
+    /// profile has never recorded one
+    calibrated_at: u64,
+    /// Calibrated thresholds, keyed by the fan's PWM sysfs path
+    thresholds: HashMap<String, Thresholds>,
+}
+
+impl Profile {
+    /// Build an empty profile, with no calibration recorded yet
+    pub(crate) fn new() -> Self {
+        Self {
+            version: PROFILE_VERSION,
+            calibrated_at: 0,
+            thresholds: HashMap::new(),
+        }
+    }
+
+    /// Load a profile from disk, validating its version and thresholds
+    pub(crate) fn load(path: &Path) -> anyhow::Result<Self> {
+        let data = fs::read_to_string(path)?;
+        let profile: Self = serde_json::from_str(&data)?;
+        anyhow::ensure!(
+            profile.version == PROFILE_VERSION,
+            ProfileError::UnsupportedVersion(profile.version)
+        );
+        for (key, thresholds) in &profile.thresholds {
+            anyhow::ensure!(
+                thresholds.max_stop < thresholds.min_start,
+                ProfileError::InvalidThresholds(
+                    key.clone(),
+                    thresholds.max_stop,
+                    thresholds.min_start
+                )
+            );
+        }
+        Ok(profile)
+    }
+
+    /// Save this profile to disk
+    pub(crate) fn save(&self, path: &Path) -> anyhow::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Previously calibrated thresholds for a fan, if any
+    fn get(&self, pwm_path: &Path) -> Option<&Thresholds> {
+        self.thresholds.get(&path_key(pwm_path))
+    }
+
+    /// Record freshly calibrated thresholds for a fan
+    fn set(&mut self, pwm_path: &Path, thresholds: Thresholds) {
+        self.thresholds.insert(path_key(pwm_path), thresholds);
+        self.calibrated_at = now_unix();
+    }
+}
+
+/// Key a fan's PWM path is stored under in the profile
+fn path_key(pwm_path: &Path) -> String {
+    pwm_path.to_string_lossy().into_owned()
+}
+
+/// Current unix timestamp in seconds
+fn now_unix() -> u64 {
+    #[expect(clippy::unwrap_used)]
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Get thresholds for `fan`, reusing a cached calibration from `profile` unless `recalibrate` is
+/// set or no cached entry exists, in which case `Fan::test` is run and the result is cached
+pub(crate) fn thresholds_for(
+    fan: &mut Fan,
+    profile: &mut Profile,
+    recalibrate: bool,
+) -> anyhow::Result<Thresholds> {
+    if !recalibrate {
+        if let Some(cached) = profile.get(fan.pwm_path()) {
+            log::info!("Reusing cached calibration for fan {fan}: {cached}");
+            return Ok(cached.clone());
+        }
+    }
+    let thresholds = fan.test()?;
+    profile.set(fan.pwm_path(), thresholds.clone());
+    Ok(thresholds)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    /// Build a path to a scratch profile file under the system temp dir, unique to this test
+    fn scratch_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "hddfancontrol-test-{name}-{:?}.json",
+            std::thread::current().id()
+        ))
+    }
+
+    #[test]
+    fn test_new_has_no_calibration_recorded() {
+        let profile = Profile::new();
+        assert_eq!(profile.calibrated_at, 0);
+        assert!(profile.thresholds.is_empty());
+    }
+
+    #[test]
+    fn test_get_set_roundtrip() {
+        let mut profile = Profile::new();
+        let pwm_path = Path::new("/sys/class/hwmon/hwmon0/pwm1");
+        assert!(profile.get(pwm_path).is_none());
+
+        let thresholds = Thresholds {
+            min_start: 200,
+            max_stop: 100,
+        };
+        profile.set(pwm_path, thresholds.clone());
+        assert_ne!(profile.calibrated_at, 0);
+        assert_eq!(
+            profile.get(pwm_path).unwrap().min_start,
+            thresholds.min_start
+        );
+        assert_eq!(profile.get(pwm_path).unwrap().max_stop, thresholds.max_stop);
+    }
+
+    #[test]
+    fn test_save_load_roundtrip() {
+        let path = scratch_path("save-load");
+        let mut profile = Profile::new();
+        profile.set(
+            Path::new("/sys/class/hwmon/hwmon0/pwm1"),
+            Thresholds {
+                min_start: 200,
+                max_stop: 100,
+            },
+        );
+
+        profile.save(&path).unwrap();
+        let loaded = Profile::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.version, PROFILE_VERSION);
+        assert_eq!(
+            loaded
+                .get(Path::new("/sys/class/hwmon/hwmon0/pwm1"))
+                .unwrap()
+                .min_start,
+            200
+        );
+    }
+
+    #[test]
+    fn test_load_rejects_unsupported_version() {
+        let path = scratch_path("bad-version");
+        fs::write(
+            &path,
+            r#"{"version":9999,"calibrated_at":0,"thresholds":{}}"#,
+        )
+        .unwrap();
+        let result = Profile::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_thresholds() {
+        let path = scratch_path("bad-thresholds");
+        fs::write(
+            &path,
+            format!(
+                r#"{{"version":{PROFILE_VERSION},"calibrated_at":0,"thresholds":{{"pwm1":{{"min_start":100,"max_stop":200}}}}}}"#
+            ),
+        )
+        .unwrap();
+        let result = Profile::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+}
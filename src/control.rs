@@ -0,0 +1,340 @@
+//! Line-delimited JSON control/status socket
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write as _},
+    os::unix::net::{UnixListener, UnixStream},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fan::{Fan, Speed, SpeedMode, Thresholds},
+    probe::Temp,
+};
+
+/// Control mode for a fan: driven by the temperature loop, or pinned to a manual speed
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ControlMode {
+    /// Speed is computed from temperature as usual
+    Auto,
+    /// Speed is pinned by a `set` command until `mode auto` is received
+    Manual,
+}
+
+/// Per-fan status reported to clients
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct FanStatus {
+    /// Fan identifier, as configured
+    pub name: String,
+    /// Current commanded speed
+    pub speed: Speed,
+    /// Measured RPM
+    pub rpm: u32,
+    /// Pwm thresholds
+    pub thresholds: Thresholds,
+    /// Current control mode
+    pub mode: ControlMode,
+}
+
+/// Snapshot of the daemon state, emitted for `report` commands
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct Report {
+    /// Per-drive temperature, keyed by drive identifier
+    pub temps: HashMap<String, Temp>,
+    /// Per-fan status
+    pub fans: Vec<FanStatus>,
+}
+
+/// Command sent by a client, one JSON object per line
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub(crate) enum Command {
+    /// Emit a single [`Report`]
+    Report,
+    /// Force a fan to a manual speed
+    Set {
+        /// Fan identifier, as configured
+        fan: String,
+        /// Speed to set
+        speed: Speed,
+    },
+    /// Switch a fan back to automatic control, or force it into manual control
+    Mode {
+        /// Fan identifier, as configured
+        fan: String,
+        /// Mode to switch to
+        mode: ControlMode,
+    },
+    /// Toggle periodic streaming of `report`-style lines on this connection
+    ReportMode {
+        /// Whether to start or stop streaming
+        enabled: bool,
+    },
+}
+
+/// Interval at which `report`-style lines are streamed once report mode is enabled
+const REPORT_STREAM_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Shared daemon state exposed to the control socket
+pub(crate) struct ControlState {
+    /// Fans, keyed by the identifier used in commands/reports
+    pub fans: HashMap<String, Fan>,
+    /// Latest known per-drive temperatures
+    pub temps: HashMap<String, Temp>,
+    /// Per-fan control mode
+    pub modes: HashMap<String, ControlMode>,
+    /// Per-fan speed mode, reset whenever a fan's `modes` entry changes
+    pub speed_modes: HashMap<String, SpeedMode>,
+}
+
+impl ControlState {
+    /// Build a [`Report`] from the current state
+    fn report(&self) -> anyhow::Result<Report> {
+        let mut fans = Vec::with_capacity(self.fans.len());
+        for (name, fan) in &self.fans {
+            fans.push(FanStatus {
+                name: name.clone(),
+                speed: fan.speed(),
+                rpm: fan.rpm()?,
+                thresholds: fan.thresholds().clone(),
+                mode: self.modes.get(name).copied().unwrap_or(ControlMode::Auto),
+            });
+        }
+        Ok(Report {
+            temps: self.temps.clone(),
+            fans,
+        })
+    }
+
+    /// Apply a single command, returning the response line to write back (if any)
+    fn apply(&mut self, command: Command) -> anyhow::Result<Option<Report>> {
+        match command {
+            Command::Report => Ok(Some(self.report()?)),
+            Command::Set { fan, speed } => {
+                let fan_state = self
+                    .fans
+                    .get_mut(&fan)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown fan: {fan}"))?;
+                let prev = self.modes.insert(fan.clone(), ControlMode::Manual);
+                if prev != Some(ControlMode::Manual) {
+                    if let Some(speed_mode) = self.speed_modes.get_mut(&fan) {
+                        speed_mode.reset();
+                    }
+                }
+                fan_state.set_speed(speed)?;
+                Ok(None)
+            }
+            Command::Mode { fan, mode } => {
+                anyhow::ensure!(self.fans.contains_key(&fan), "Unknown fan: {fan}");
+                let prev = self.modes.insert(fan.clone(), mode);
+                if prev != Some(mode) {
+                    if let Some(speed_mode) = self.speed_modes.get_mut(&fan) {
+                        speed_mode.reset();
+                    }
+                }
+                Ok(None)
+            }
+            // Handled directly by the connection handler, which owns the streaming toggle
+            Command::ReportMode { .. } => Ok(None),
+        }
+    }
+}
+
+/// Handle a single client connection, reading newline-delimited JSON commands and writing back
+/// newline-delimited JSON responses. Ticks a periodic `report` line via `REPORT_STREAM_INTERVAL`
+/// read timeouts while report mode is enabled, in the same loop as command reads, so the
+/// connection's lifetime (not a timer) governs how long this runs
+fn handle_client(stream: UnixStream, state: &Arc<Mutex<ControlState>>) -> anyhow::Result<()> {
+    stream.set_read_timeout(Some(REPORT_STREAM_INTERVAL))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    let mut streaming = false;
+    // Not cleared on a read timeout: a command line split across a >REPORT_STREAM_INTERVAL gap
+    // must keep accumulating here instead of being dropped.
+    let mut line = String::new();
+
+    loop {
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {
+                if line.trim().is_empty() {
+                    line.clear();
+                    continue;
+                }
+                let command: Command = serde_json::from_str(&line)?;
+                line.clear();
+                if let Command::ReportMode { enabled } = command {
+                    streaming = enabled;
+                    continue;
+                }
+                #[expect(clippy::unwrap_used)]
+                let response = state.lock().unwrap().apply(command)?;
+                if let Some(report) = response {
+                    writeln!(writer, "{}", serde_json::to_string(&report)?)?;
+                }
+            }
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) =>
+            {
+                if streaming {
+                    #[expect(clippy::unwrap_used)]
+                    let report = state.lock().unwrap().report()?;
+                    writeln!(writer, "{}", serde_json::to_string(&report)?)?;
+                }
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(())
+}
+
+/// Run the control socket server, accepting connections until the process exits
+pub(crate) fn serve(socket_path: &Path, state: Arc<Mutex<ControlState>>) -> anyhow::Result<()> {
+    let _ = std::fs::remove_file(socket_path);
+    let listener = UnixListener::bind(socket_path)?;
+    log::info!("Control socket listening on {}", socket_path.display());
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let state = Arc::clone(&state);
+        std::thread::spawn(move || {
+            if let Err(e) = handle_client(stream, &state) {
+                log::warn!("Control socket client error: {e}");
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cl::PwmSettings,
+        fan::{PidController, Thresholds},
+        pwm::tests::FakePwm,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_command_deserialize() {
+        assert!(matches!(
+            serde_json::from_str::<Command>(r#"{"command":"report"}"#).unwrap(),
+            Command::Report
+        ));
+        assert!(matches!(
+            serde_json::from_str::<Command>(r#"{"command":"set","fan":"fan0","speed":0.5}"#)
+                .unwrap(),
+            Command::Set { fan, speed }
+                if fan == "fan0" && speed == Speed::try_from(0.5).unwrap()
+        ));
+        assert!(matches!(
+            serde_json::from_str::<Command>(r#"{"command":"mode","fan":"fan0","mode":"manual"}"#)
+                .unwrap(),
+            Command::Mode {
+                fan,
+                mode: ControlMode::Manual
+            } if fan == "fan0"
+        ));
+        assert!(matches!(
+            serde_json::from_str::<Command>(r#"{"command":"report_mode","enabled":true}"#).unwrap(),
+            Command::ReportMode { enabled: true }
+        ));
+    }
+
+    fn test_state() -> (FakePwm, ControlState) {
+        let fake_pwm = FakePwm::new();
+        let fan = Fan::new(&PwmSettings {
+            filepath: fake_pwm.pwm_path.clone(),
+            thresholds: Thresholds {
+                min_start: 200,
+                max_stop: 100,
+            },
+        })
+        .unwrap();
+        let mut fans = HashMap::new();
+        fans.insert("fan0".to_owned(), fan);
+        let mut temps = HashMap::new();
+        temps.insert("sda".to_owned(), 42.0);
+        (
+            fake_pwm,
+            ControlState {
+                fans,
+                temps,
+                modes: HashMap::new(),
+                speed_modes: HashMap::new(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_control_state_report() {
+        let (_fake_pwm, state) = test_state();
+        let report = state.report().unwrap();
+        assert_eq!(report.temps.get("sda"), Some(&42.0));
+        assert_eq!(report.fans.len(), 1);
+        assert_eq!(report.fans[0].name, "fan0");
+        assert_eq!(report.fans[0].mode, ControlMode::Auto);
+    }
+
+    #[test]
+    fn test_control_state_set_switches_to_manual() {
+        use std::io::Write as _;
+
+        let (mut fake_pwm, mut state) = test_state();
+        fake_pwm.mode_file_write.write_all(b"1\n").unwrap();
+        state
+            .apply(Command::Set {
+                fan: "fan0".to_owned(),
+                speed: Speed::try_from(1.0).unwrap(),
+            })
+            .unwrap();
+        assert_eq!(state.modes.get("fan0"), Some(&ControlMode::Manual));
+    }
+
+    #[test]
+    fn test_control_state_mode_unknown_fan_errors() {
+        let (_fake_pwm, mut state) = test_state();
+        assert!(state
+            .apply(Command::Mode {
+                fan: "missing".to_owned(),
+                mode: ControlMode::Auto,
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_control_state_mode_switch_resets_speed_mode() {
+        let (_fake_pwm, mut state) = test_state();
+        state.speed_modes.insert(
+            "fan0".to_owned(),
+            SpeedMode::Pid(PidController::new(40.0, 0.1, 0.0, 0.0)),
+        );
+        {
+            let SpeedMode::Pid(pid) = state.speed_modes.get_mut("fan0").unwrap() else {
+                unreachable!()
+            };
+            pid.update(50.0);
+            assert_ne!(pid.integral, 0.0);
+        }
+
+        state
+            .apply(Command::Mode {
+                fan: "fan0".to_owned(),
+                mode: ControlMode::Manual,
+            })
+            .unwrap();
+
+        let SpeedMode::Pid(pid) = state.speed_modes.get("fan0").unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(pid.integral, 0.0);
+    }
+}
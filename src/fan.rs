@@ -18,7 +18,7 @@ use crate::{
 const STARTUP_DELAY: Duration = Duration::from_secs(20);
 
 /// Fan characteristics
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub(crate) struct Thresholds {
     /// Minimum value at which the fan starts moving when it was stopped
     pub min_start: pwm::Value,
@@ -61,6 +61,25 @@ impl Speed {
     }
 }
 
+impl serde::Serialize for Speed {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_f64(self.0.get())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Speed {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = f64::deserialize(deserializer)?;
+        Speed::try_from(value).map_err(serde::de::Error::custom)
+    }
+}
+
 #[expect(clippy::missing_docs_in_private_items)]
 #[derive(thiserror::Error, Debug)]
 pub(crate) enum SpeedConversionError {
@@ -160,6 +179,11 @@ impl Fan {
         Ok(())
     }
 
+    /// Compute the target speed for `temp` from `mode` and apply it
+    pub(crate) fn update_speed(&mut self, temp: Temp, mode: &mut SpeedMode) -> anyhow::Result<()> {
+        self.set_speed(mode.eval(temp))
+    }
+
     /// Wait until fan speed stop increasing or decreasing
     fn wait_stable(&self, change: SpeedChange) -> anyhow::Result<()> {
         /// Maximum duration to wait for the fan to be stabilized
@@ -204,6 +228,27 @@ impl Fan {
         Ok(self.pwm.get_rpm()? > 0)
     }
 
+    /// Current commanded speed, or zero if the fan has not been driven yet
+    pub(crate) fn speed(&self) -> Speed {
+        #[expect(clippy::unwrap_used)]
+        self.speed.unwrap_or_else(|| Speed::try_from(0.0).unwrap())
+    }
+
+    /// Measured RPM
+    pub(crate) fn rpm(&self) -> anyhow::Result<u32> {
+        self.pwm.get_rpm()
+    }
+
+    /// Current pwm thresholds
+    pub(crate) fn thresholds(&self) -> &Thresholds {
+        &self.thresholds
+    }
+
+    /// Filesystem path identifying this fan's PWM, used as a calibration profile key
+    pub(crate) fn pwm_path(&self) -> &std::path::Path {
+        self.pwm.path()
+    }
+
     /// Dynamically test fan to find its thresholds
     pub(crate) fn test(&mut self) -> anyhow::Result<Thresholds> {
         self.set_speed(1.0.try_into()?)?;
@@ -239,6 +284,109 @@ impl Fan {
     }
 }
 
+/// A single control point of a [`Curve`], mapping a temperature to a fan speed
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct CurvePoint {
+    /// Temperature at this point
+    pub temp: Temp,
+    /// Speed to reach at this point
+    pub speed: Speed,
+}
+
+/// Error building a [`Curve`]
+#[expect(clippy::missing_docs_in_private_items)]
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum CurveError {
+    #[error("Curve must have at least one point")]
+    Empty,
+    #[error("Curve points temperatures must be strictly increasing")]
+    NotSorted,
+}
+
+/// Piecewise-linear fan curve defined by a sorted list of temperature/speed control points
+#[derive(Clone, Debug)]
+pub(crate) struct Curve(Vec<CurvePoint>);
+
+impl Curve {
+    /// Build a new curve from control points, which must be given in strictly ascending
+    /// temperature order (they are not sorted here)
+    pub(crate) fn new(points: Vec<CurvePoint>) -> Result<Self, CurveError> {
+        if points.is_empty() {
+            return Err(CurveError::Empty);
+        }
+        if points.windows(2).any(|w| w[0].temp >= w[1].temp) {
+            return Err(CurveError::NotSorted);
+        }
+        Ok(Self(points))
+    }
+
+    /// Evaluate target speed for a given temperature, interpolating between the bracketing points
+    pub(crate) fn eval(&self, temp: Temp) -> Speed {
+        #[expect(clippy::unwrap_used)]
+        match self.0.partition_point(|p| p.temp <= temp) {
+            0 => self.0[0].speed,
+            i if i == self.0.len() => self.0[self.0.len() - 1].speed,
+            i => {
+                let (p0, p1) = (&self.0[i - 1], &self.0[i]);
+                let ratio = (temp - p0.temp) / (p1.temp - p0.temp);
+                Speed::try_from(p0.speed.0.get() + (p1.speed.0.get() - p0.speed.0.get()) * ratio)
+                    .unwrap()
+            }
+        }
+    }
+}
+
+/// How a fan derives its target speed from a probed temperature
+pub(crate) enum SpeedMode {
+    /// Single linear ramp over a temperature range, floored by a minimum speed (the default)
+    Linear {
+        /// Temperature range the ramp spans
+        temp_range: Range<Temp>,
+        /// Speed floor
+        min_speed: Speed,
+    },
+    /// Piecewise-linear multi-point curve
+    Curve(Curve),
+    /// Quadratic polynomial over a temperature range, floored by a minimum speed
+    Polynomial {
+        /// Temperature range the polynomial is normalized over
+        temp_range: Range<Temp>,
+        /// Speed floor
+        min_speed: Speed,
+        /// Polynomial coefficients
+        coeffs: PolynomialCoeffs,
+    },
+    /// PID loop driving towards a temperature setpoint
+    Pid(PidController),
+}
+
+impl SpeedMode {
+    /// Compute the target speed for a given temperature
+    pub(crate) fn eval(&mut self, temp: Temp) -> Speed {
+        match self {
+            Self::Linear {
+                temp_range,
+                min_speed,
+            } => target_speed(temp, temp_range, *min_speed),
+            Self::Curve(curve) => curve.eval(temp),
+            Self::Polynomial {
+                temp_range,
+                min_speed,
+                coeffs,
+            } => target_speed_polynomial(temp, temp_range, *min_speed, coeffs),
+            Self::Pid(pid) => pid.update(temp),
+        }
+    }
+
+    /// Reset any accumulated regulation state (currently only meaningful for [`Self::Pid`]), to
+    /// be called whenever a fan switches in or out of this mode so it doesn't jump
+    pub(crate) fn reset(&mut self) {
+        if let Self::Pid(pid) = self {
+            pid.reset();
+        }
+    }
+}
+
 /// Compute target fan speed for the given temp and parameters
 pub(crate) fn target_speed(temp: Temp, temp_range: &Range<Temp>, min_speed: Speed) -> Speed {
     if temp_range.contains(&temp) {
@@ -254,6 +402,105 @@ pub(crate) fn target_speed(temp: Temp, temp_range: &Range<Temp>, min_speed: Spee
     }
 }
 
+/// Quadratic fan curve coefficients, evaluated as `a*x² + b*x + c` where `x` is the temperature
+/// normalized to `[0, 1]` over a `temp_range`
+///
+/// `PwmSettings` carries no polynomial coefficients, so they cannot be set per fan from the
+/// command line.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct PolynomialCoeffs {
+    /// Quadratic coefficient
+    pub a: f64,
+    /// Linear coefficient
+    pub b: f64,
+    /// Constant coefficient
+    pub c: f64,
+}
+
+/// Compute target fan speed from polynomial coefficients for the given temp and parameters
+pub(crate) fn target_speed_polynomial(
+    temp: Temp,
+    temp_range: &Range<Temp>,
+    min_speed: Speed,
+    coeffs: &PolynomialCoeffs,
+) -> Speed {
+    let x = ((temp - temp_range.start) / (temp_range.end - temp_range.start)).clamp(0.0, 1.0);
+    let y = coeffs.a * x * x + coeffs.b * x + coeffs.c;
+    let s = Speed::try_from(y.clamp(0.0, 1.0)).unwrap_or_else(|_| {
+        #[expect(clippy::unwrap_used)]
+        Speed::try_from(0.0).unwrap()
+    });
+    max(min_speed, s)
+}
+
+/// PID-based temperature regulator, driving fan speed towards a target temperature setpoint
+#[derive(Debug)]
+pub(crate) struct PidController {
+    /// Target temperature
+    setpoint: Temp,
+    /// Proportional gain
+    kp: f64,
+    /// Integral gain
+    ki: f64,
+    /// Derivative gain
+    kd: f64,
+    /// Accumulated integral term
+    pub(crate) integral: f64,
+    /// Previous error, used to compute the derivative term
+    prev_error: Option<f64>,
+    /// Timestamp of the last update
+    last_update: Instant,
+}
+
+impl PidController {
+    /// Build a new PID controller targeting `setpoint`
+    pub(crate) fn new(setpoint: Temp, kp: f64, ki: f64, kd: f64) -> Self {
+        Self {
+            setpoint,
+            kp,
+            ki,
+            kd,
+            integral: 0.0,
+            prev_error: None,
+            last_update: Instant::now(),
+        }
+    }
+
+    /// Reset accumulated integral/derivative state, e.g. when switching back into PID mode so the
+    /// fan doesn't jump from stale state
+    pub(crate) fn reset(&mut self) {
+        self.integral = 0.0;
+        self.prev_error = None;
+        self.last_update = Instant::now();
+    }
+
+    /// Compute the next output speed for the given temperature reading
+    pub(crate) fn update(&mut self, temp: Temp) -> Speed {
+        let now = Instant::now();
+        let dt = now
+            .duration_since(self.last_update)
+            .as_secs_f64()
+            .max(f64::EPSILON);
+        let error = temp - self.setpoint;
+
+        let candidate_integral = self.integral + error * dt;
+        let derivative = self.prev_error.map_or(0.0, |prev| (error - prev) / dt);
+        let output = self.kp * error + self.ki * candidate_integral + self.kd * derivative;
+        let clamped = output.clamp(0.0, 1.0);
+
+        // Anti-windup: only keep accumulating the integral while the output is not saturated
+        if (output - clamped).abs() < f64::EPSILON {
+            self.integral = candidate_integral;
+        }
+
+        self.prev_error = Some(error);
+        self.last_update = now;
+
+        #[expect(clippy::unwrap_used)]
+        Speed::try_from(clamped).unwrap()
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -343,6 +590,204 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pid_controller() {
+        let mut pid = PidController::new(40.0, 0.1, 0.0, 0.0);
+
+        // Above setpoint: positive error, positive output
+        let speed = pid.update(50.0);
+        assert!(speed > Speed::try_from(0.0).unwrap());
+
+        // Below setpoint: negative error clamps to zero speed
+        let speed = pid.update(30.0);
+        assert_eq!(speed, Speed::try_from(0.0).unwrap());
+
+        pid.reset();
+        assert_eq!(pid.integral, 0.0);
+        assert_eq!(pid.prev_error, None);
+    }
+
+    #[test]
+    fn test_target_speed_polynomial() {
+        let temp_range = Range {
+            start: 40.0,
+            end: 50.0,
+        };
+        let min_speed = Speed::try_from(0.2).unwrap();
+        // y = x^2, so at x=0.5 -> 0.25
+        let coeffs = PolynomialCoeffs {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+        };
+        assert_eq!(
+            target_speed_polynomial(45.0, &temp_range, min_speed, &coeffs),
+            Speed::try_from(0.25).unwrap()
+        );
+        // below range clamps x to 0 -> y=0, floored by min_speed
+        assert_eq!(
+            target_speed_polynomial(30.0, &temp_range, min_speed, &coeffs),
+            min_speed
+        );
+        // above range clamps x to 1 -> y=1
+        assert_eq!(
+            target_speed_polynomial(60.0, &temp_range, min_speed, &coeffs),
+            Speed::try_from(1.0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_curve_new() {
+        assert!(Curve::new(vec![]).is_err());
+        assert!(Curve::new(vec![CurvePoint {
+            temp: 40.0,
+            speed: Speed::try_from(0.2).unwrap()
+        }])
+        .is_ok());
+        assert!(Curve::new(vec![
+            CurvePoint {
+                temp: 40.0,
+                speed: Speed::try_from(0.2).unwrap()
+            },
+            CurvePoint {
+                temp: 40.0,
+                speed: Speed::try_from(0.5).unwrap()
+            }
+        ])
+        .is_err());
+        assert!(Curve::new(vec![
+            CurvePoint {
+                temp: 50.0,
+                speed: Speed::try_from(0.2).unwrap()
+            },
+            CurvePoint {
+                temp: 40.0,
+                speed: Speed::try_from(0.5).unwrap()
+            }
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_curve_eval() {
+        let curve = Curve::new(vec![
+            CurvePoint {
+                temp: 30.0,
+                speed: Speed::try_from(0.1).unwrap(),
+            },
+            CurvePoint {
+                temp: 40.0,
+                speed: Speed::try_from(0.5).unwrap(),
+            },
+            CurvePoint {
+                temp: 50.0,
+                speed: Speed::try_from(1.0).unwrap(),
+            },
+        ])
+        .unwrap();
+
+        assert_eq!(curve.eval(20.0), Speed::try_from(0.1).unwrap());
+        assert_eq!(curve.eval(30.0), Speed::try_from(0.1).unwrap());
+        assert!((curve.eval(35.0).0.get() - 0.3).abs() < 1e-9);
+        assert_eq!(curve.eval(50.0), Speed::try_from(1.0).unwrap());
+        assert_eq!(curve.eval(60.0), Speed::try_from(1.0).unwrap());
+
+        let constant = Curve::new(vec![CurvePoint {
+            temp: 40.0,
+            speed: Speed::try_from(0.3).unwrap(),
+        }])
+        .unwrap();
+        assert_eq!(constant.eval(20.0), Speed::try_from(0.3).unwrap());
+        assert_eq!(constant.eval(60.0), Speed::try_from(0.3).unwrap());
+    }
+
+    #[test]
+    fn test_update_speed_curve() {
+        let mut fake_pwm = FakePwm::new();
+        let mut fan = Fan::new(&PwmSettings {
+            filepath: fake_pwm.pwm_path.clone(),
+            thresholds: Thresholds {
+                min_start: 200,
+                max_stop: 100,
+            },
+        })
+        .unwrap();
+        let mut mode = SpeedMode::Curve(
+            Curve::new(vec![
+                CurvePoint {
+                    temp: 40.0,
+                    speed: Speed::try_from(0.0).unwrap(),
+                },
+                CurvePoint {
+                    temp: 50.0,
+                    speed: Speed::try_from(1.0).unwrap(),
+                },
+            ])
+            .unwrap(),
+        );
+
+        fake_pwm.mode_file_write.write_all(b"1\n").unwrap();
+        fan.update_speed(50.0, &mut mode).unwrap();
+        assert_file_content(&mut fake_pwm.val_file_read, "255\n");
+    }
+
+    #[test]
+    fn test_update_speed_polynomial() {
+        let mut fake_pwm = FakePwm::new();
+        let mut fan = Fan::new(&PwmSettings {
+            filepath: fake_pwm.pwm_path.clone(),
+            thresholds: Thresholds {
+                min_start: 200,
+                max_stop: 100,
+            },
+        })
+        .unwrap();
+        let mut mode = SpeedMode::Polynomial {
+            temp_range: Range {
+                start: 40.0,
+                end: 50.0,
+            },
+            min_speed: Speed::try_from(0.2).unwrap(),
+            coeffs: PolynomialCoeffs {
+                a: 1.0,
+                b: 0.0,
+                c: 0.0,
+            },
+        };
+
+        fake_pwm.mode_file_write.write_all(b"1\n").unwrap();
+        fan.update_speed(50.0, &mut mode).unwrap();
+        assert_file_content(&mut fake_pwm.val_file_read, "255\n");
+    }
+
+    #[test]
+    fn test_update_speed_pid_and_reset() {
+        let mut fake_pwm = FakePwm::new();
+        let mut fan = Fan::new(&PwmSettings {
+            filepath: fake_pwm.pwm_path.clone(),
+            thresholds: Thresholds {
+                min_start: 200,
+                max_stop: 100,
+            },
+        })
+        .unwrap();
+        let mut mode = SpeedMode::Pid(PidController::new(40.0, 0.1, 0.0, 0.0));
+
+        fake_pwm.mode_file_write.write_all(b"1\n").unwrap();
+        fan.update_speed(50.0, &mut mode).unwrap();
+
+        let SpeedMode::Pid(ref pid) = mode else {
+            unreachable!()
+        };
+        assert_ne!(pid.integral, 0.0);
+
+        mode.reset();
+        let SpeedMode::Pid(ref pid) = mode else {
+            unreachable!()
+        };
+        assert_eq!(pid.integral, 0.0);
+    }
+
     #[test]
     fn test_set_speed() {
         let mut fake_pwm = FakePwm::new();
@@ -35,12 +35,65 @@ pub trait DriveTempProber {
     fn probe_temp(&mut self) -> anyhow::Result<Temp>;
 }
 
-/// Find first supported prober for a drive
-pub fn prober(drive: &Drive) -> anyhow::Result<Option<Box<dyn DriveTempProber>>> {
-    let methods: [Box<dyn DriveTempProbeMethod>; 1] = [Box::new(drivetemp::Method)];
-    for method in methods {
+/// Identifies a [`DriveTempProbeMethod`] implementation, to let users configure probing order
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ProbeMethodKind {
+    /// Linux `drivetemp` hwmon driver
+    Drivetemp,
+    /// `hddtemp` daemon/database
+    Hddtemp,
+    /// `hdparm` SMART temperature attribute
+    Hdparm,
+    /// `smartctl` SMART temperature attribute
+    Smartctl,
+}
+
+impl ProbeMethodKind {
+    /// Default order in which probing methods are tried
+    pub const DEFAULT_ORDER: [Self; 4] =
+        [Self::Drivetemp, Self::Hddtemp, Self::Hdparm, Self::Smartctl];
+
+    /// Build the probe method implementation for this kind
+    fn method(self) -> Box<dyn DriveTempProbeMethod> {
+        match self {
+            Self::Drivetemp => Box::new(drivetemp::Method),
+            Self::Hddtemp => Box::new(hddtemp::Method),
+            Self::Hdparm => Box::new(hdparm::Method),
+            Self::Smartctl => Box::new(smartctl::Method),
+        }
+    }
+}
+
+/// Resolve the probing order to use: `order` as given, or [`ProbeMethodKind::DEFAULT_ORDER`] if
+/// empty (e.g. unset in CLI/config)
+fn resolve_order(order: &[ProbeMethodKind]) -> &[ProbeMethodKind] {
+    if order.is_empty() {
+        &ProbeMethodKind::DEFAULT_ORDER
+    } else {
+        order
+    }
+}
+
+/// Find first supported prober for a drive, trying each method of `order` in turn (or
+/// [`ProbeMethodKind::DEFAULT_ORDER`] if empty)
+pub fn prober(
+    drive: &Drive,
+    order: &[ProbeMethodKind],
+) -> anyhow::Result<Option<Box<dyn DriveTempProber>>> {
+    for kind in resolve_order(order) {
+        let method = kind.method();
         match method.prober(drive) {
-            Ok(p) => return Ok(Some(p)),
+            Ok(mut p) => match p.probe_temp() {
+                Ok(_) => return Ok(Some(p)),
+                Err(e) => {
+                    log::warn!(
+                        "Drive '{}' reported support for probing method '{}' but a smoke test probe failed: {}",
+                        drive,
+                        method,
+                        e
+                    );
+                }
+            },
             Err(ProberError::Unsupported(e)) => {
                 log::info!(
                     "Drive '{}' does not support probing method '{}': {}",
@@ -54,3 +107,21 @@ pub fn prober(drive: &Drive) -> anyhow::Result<Option<Box<dyn DriveTempProber>>>
     }
     Ok(None)
 }
+
+// The fallback/smoke-check loop of `prober` is not covered by these tests, which exercise
+// `resolve_order` only.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_order_empty_defaults() {
+        assert_eq!(resolve_order(&[]), &ProbeMethodKind::DEFAULT_ORDER);
+    }
+
+    #[test]
+    fn test_resolve_order_custom() {
+        let custom = [ProbeMethodKind::Smartctl, ProbeMethodKind::Hdparm];
+        assert_eq!(resolve_order(&custom), &custom);
+    }
+}